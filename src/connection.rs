@@ -0,0 +1,117 @@
+use ethers::providers::{Http, Middleware, Provider, Quorum, QuorumProvider, Ws};
+use ethers::types::H256;
+use std::env;
+use std::str::FromStr;
+use std::time::Duration;
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Connects to `url` over WebSocket, retrying with exponential backoff
+/// (capped at `MAX_BACKOFF`) until it succeeds. A long-running listener
+/// should reconnect on a dropped socket, not die with `TrackerError::WebSocketConnection`.
+pub async fn connect_ws_with_backoff(url: &str) -> Provider<Ws> {
+    let mut backoff = INITIAL_BACKOFF;
+    loop {
+        match Ws::connect(url).await {
+            Ok(ws) => return Provider::new(ws),
+            Err(e) => {
+                eprintln!(
+                    "WebSocket connection to {} failed: {:?}; retrying in {:?}",
+                    url, e, backoff
+                );
+                tokio::time::sleep(backoff).await;
+                backoff = std::cmp::min(backoff * 2, MAX_BACKOFF);
+            }
+        }
+    }
+}
+
+/// Reads `RPC_QUORUM_URLS` (comma-separated HTTP endpoints) and, if set,
+/// builds a quorum-backed provider so a candidate pending hash / log is only
+/// accepted once `RPC_QUORUM_THRESHOLD` of them agree (defaults to all of
+/// them). Returns `None` when no quorum endpoints are configured, so callers
+/// fall back to trusting the primary WebSocket connection alone.
+pub fn build_quorum_provider() -> Option<Provider<QuorumProvider<Http>>> {
+    let urls_env = env::var("RPC_QUORUM_URLS").ok()?;
+    let urls = parse_quorum_urls(&urls_env);
+    if urls.is_empty() {
+        return None;
+    }
+
+    let threshold = resolve_quorum_threshold(env::var("RPC_QUORUM_THRESHOLD").ok().as_deref(), urls.len());
+
+    let providers: Vec<_> = urls
+        .iter()
+        .filter_map(|url| Http::from_str(url).ok())
+        .map(ethers::providers::WeightedProvider::new)
+        .collect();
+
+    if providers.is_empty() {
+        return None;
+    }
+
+    let quorum = QuorumProvider::builder()
+        .add_providers(providers)
+        .quorum(Quorum::ProviderCount(threshold))
+        .build();
+
+    Some(Provider::new(quorum))
+}
+
+/// Splits `RPC_QUORUM_URLS`'s raw value into trimmed, non-empty endpoints.
+fn parse_quorum_urls(urls_env: &str) -> Vec<String> {
+    urls_env
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Resolves `RPC_QUORUM_THRESHOLD` against the number of configured
+/// endpoints: an unset or unparseable value defaults to requiring all of
+/// them to agree.
+fn resolve_quorum_threshold(raw: Option<&str>, url_count: usize) -> usize {
+    raw.and_then(|s| s.parse().ok()).unwrap_or(url_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_quorum_urls_trims_and_drops_empty_entries() {
+        let urls = parse_quorum_urls(" http://a , http://b,, http://c ");
+        assert_eq!(urls, vec!["http://a", "http://b", "http://c"]);
+    }
+
+    #[test]
+    fn parse_quorum_urls_empty_input_yields_no_urls() {
+        assert!(parse_quorum_urls("").is_empty());
+    }
+
+    #[test]
+    fn resolve_quorum_threshold_defaults_to_url_count() {
+        assert_eq!(resolve_quorum_threshold(None, 3), 3);
+        assert_eq!(resolve_quorum_threshold(Some("not-a-number"), 3), 3);
+    }
+
+    #[test]
+    fn resolve_quorum_threshold_uses_explicit_value() {
+        assert_eq!(resolve_quorum_threshold(Some("2"), 4), 2);
+    }
+}
+
+/// Confirms that `tx_hash` is also visible to the configured quorum of RPC
+/// backends, filtering out hashes that only a single (possibly reorg'd or
+/// spoofed) mempool view produced. Always `true` when no quorum is configured.
+pub async fn confirm_via_quorum(
+    quorum: &Option<Provider<QuorumProvider<Http>>>,
+    tx_hash: H256,
+) -> bool {
+    match quorum {
+        None => true,
+        Some(provider) => matches!(provider.get_transaction(tx_hash).await, Ok(Some(_))),
+    }
+}
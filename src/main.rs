@@ -1,15 +1,22 @@
 use ethers::prelude::*;
-use ethers::providers::{Provider, Ws};
-use ethers::types::{Address, Transaction, U256};
-use ethers::utils::hex;
+use ethers::types::{Address, Transaction};
 use futures_util::stream::StreamExt;
 use thiserror::Error;
 use std::env;
+use std::sync::Arc;
 use tokio;
 
+mod connection;
+mod metadata;
+mod pool_logs;
+mod router;
+mod trace;
+
+use router::SwapInfo;
+
 // Typed errors for improved diagnostics
 #[derive(Error, Debug)]
-enum TrackerError {
+pub(crate) enum TrackerError {
     #[error("WebSocket connection error: {0}")]
     WebSocketConnection(#[from] WsClientError),
 
@@ -33,57 +40,155 @@ async fn main() -> Result<(), TrackerError> {
         .parse()
         .map_err(|_| TrackerError::TransactionParsing(format!("Invalid address format: {}", input)))?;
 
-    // Create a WebSocket provider for connecting to Ethereum through Infura
-    let ws = Ws::connect(infura_ws_url).await?;
-    let provider = Provider::new(ws);
+    // Best-effort verified contract name (e.g. "SwapRouter02") via Etherscan, when
+    // `ETHERSCAN_API_KEY` is configured.
+    if let Some(label) = metadata::label_known_contract(target_contract_address).await {
+        println!("Target contract {:?} is known as: {}", target_contract_address, label);
+    }
+
+    // `RPC_WS_URL` overrides the hardcoded Infura endpoint used for the
+    // primary subscription (auto-reconnected with backoff on disconnect).
+    let rpc_ws_url = env::var("RPC_WS_URL").unwrap_or_else(|_| infura_ws_url.to_string());
+
+    // `RPC_QUORUM_URLS` (comma-separated HTTP endpoints) additionally guards
+    // against a single reorg'd or spoofed mempool view: a candidate hash is
+    // only accepted once a quorum of these agree it exists.
+    let quorum_provider = connection::build_quorum_provider();
+
+    // `LISTENER_MODE=logs` streams confirmed pool `Swap` events directly
+    // instead of scraping pending transactions (the default, `pending-tx`).
+    let mode = env::var("LISTENER_MODE").unwrap_or_else(|_| "pending-tx".to_string());
+    if mode == "logs" {
+        // `pool_logs::run` only ever returns on a dropped connection (it
+        // has no success exit condition), so every return here means
+        // "reconnect", same as the pending-tx stream below.
+        loop {
+            let provider = Arc::new(connection::connect_ws_with_backoff(&rpc_ws_url).await);
+            if let Err(e) = pool_logs::run(provider, target_contract_address, &quorum_provider).await {
+                eprintln!("Log subscription dropped ({:?}); reconnecting...", e);
+            }
+        }
+    }
 
-    // Subscribe to new transactions in the network
-    let mut stream = provider.subscribe_pending_txs().await?;
     let mut transactions: Vec<Transaction> = Vec::new();
 
-    println!("Waiting for new transactions...");
-    while let Some(tx_hash) = stream.next().await {
-        match provider.get_transaction(tx_hash).await {
-            Ok(Some(tx)) => {
-                // Filter transactions to track only those interacting with the specified contract
-                if let Some(to_address) = tx.to {
-                    // Output for debugging, indicating that the transaction is being analyzed
-                    println!("Analyzing transaction with address: {:?}", to_address);
-
-                    if to_address == target_contract_address {
-                        transactions.push(tx);
-
-                        // Example: stop after collecting 5 transactions for demonstration
-                        if transactions.len() >= 5 {
-                            break;
+    'reconnect: loop {
+        let provider = Arc::new(connection::connect_ws_with_backoff(&rpc_ws_url).await);
+        let mut stream = match provider.subscribe_pending_txs().await {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("Failed to subscribe to pending transactions: {:?}; reconnecting...", e);
+                continue 'reconnect;
+            }
+        };
+
+        println!("Waiting for new transactions...");
+        while let Some(tx_hash) = stream.next().await {
+            if !connection::confirm_via_quorum(&quorum_provider, tx_hash).await {
+                println!("Skipping {:?}: not confirmed by RPC quorum", tx_hash);
+                continue;
+            }
+
+            match provider.get_transaction(tx_hash).await {
+                Ok(Some(tx)) => {
+                    // Filter transactions to track only those interacting with the specified contract
+                    if let Some(to_address) = tx.to {
+                        // Output for debugging, indicating that the transaction is being analyzed
+                        println!("Analyzing transaction with address: {:?}", to_address);
+
+                        if to_address == target_contract_address {
+                            transactions.push(tx);
+
+                            // Example: stop after collecting 5 transactions for demonstration
+                            if transactions.len() >= 5 {
+                                break 'reconnect;
+                            }
                         }
                     }
                 }
+                Ok(None) => continue, // Transaction not found
+                Err(e) => {
+                    eprintln!("Error retrieving transaction: {:?}", e);
+                    continue;
+                }
             }
-            Ok(None) => continue, // Transaction not found
+        }
+
+        // The stream ended, meaning the WebSocket dropped; reconnect and
+        // keep accumulating into the same `transactions` buffer.
+        eprintln!("Pending-tx stream ended; reconnecting...");
+    }
+
+    let provider = Arc::new(connection::connect_ws_with_backoff(&rpc_ws_url).await);
+    let mut resolver = metadata::TokenMetadataResolver::new(provider);
+
+    // Rank by decimal-normalized token amount rather than raw `tx.value`,
+    // since most Uniswap swaps carry zero ETH value. A `multicall` may
+    // decode to several swap legs; rank by the largest one.
+    let mut ranked = Vec::with_capacity(transactions.len());
+    for tx in transactions {
+        let swaps = match router::decode_swap_calldata(&tx.input) {
+            Ok(swaps) => swaps,
             Err(e) => {
-                eprintln!("Error retrieving transaction: {:?}", e);
-                continue;
+                eprintln!("Error parsing token data: {:?}", e);
+                Vec::new()
+            }
+        };
+
+        let mut rank = 0.0;
+        for swap in &swaps {
+            let value = swap_rank_value(swap, &mut resolver).await;
+            if value > rank {
+                rank = value;
             }
         }
-    }
+        if swaps.is_empty() {
+            rank = ethers::utils::format_units(tx.value, "ether")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0.0);
+        }
 
-    // Sort transactions by amount (`value`)
-    transactions.sort_by(|a, b| a.value.cmp(&b.value));
+        ranked.push((tx, swaps, rank));
+    }
+    ranked.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(std::cmp::Ordering::Equal));
 
     // Output sorted transactions
     println!(
         "Sorted transactions related to contract {}:",
         target_contract_address
     );
-    for tx in &transactions {
-        print_transaction_info(tx);
+    for (tx, swaps, _) in &ranked {
+        print_transaction_info(tx, swaps, &mut resolver).await;
     }
 
     Ok(())
 }
 
-fn print_transaction_info(tx: &Transaction) {
+/// Decimal-normalized rank for a single swap leg, preferring the input side
+/// (known for `exactInput*` calls) and falling back to the output side
+/// (known for `exactOutput*` calls, whose `amount_in` is always `None` —
+/// without this fallback those swaps would rank as if they moved ~0 value).
+async fn swap_rank_value<M: Middleware + 'static>(
+    swap: &SwapInfo,
+    resolver: &mut metadata::TokenMetadataResolver<M>,
+) -> f64 {
+    if let Some((token, amount)) = swap.token_in.zip(swap.amount_in) {
+        let token_metadata = resolver.resolve(token).await;
+        return metadata::normalized_amount(amount, token_metadata.decimals);
+    }
+    if let Some((token, amount)) = swap.token_out.zip(swap.amount_out) {
+        let token_metadata = resolver.resolve(token).await;
+        return metadata::normalized_amount(amount, token_metadata.decimals);
+    }
+    0.0
+}
+
+async fn print_transaction_info<M: Middleware + 'static>(
+    tx: &Transaction,
+    swaps: &[SwapInfo],
+    resolver: &mut metadata::TokenMetadataResolver<M>,
+) {
     println!("==================== Transaction Details ====================");
 
     // Basic transaction information
@@ -97,7 +202,7 @@ fn print_transaction_info(tx: &Transaction) {
     println!("Value Transferred (ETH): {:?}", ethers::utils::format_units(tx.value, "ether").unwrap_or_else(|_| "N/A".to_string()));
 
     // Additional transaction details
-    println!("Gas Price (Gwei): {:?}", ethers::utils::format_units(tx.gas_price.unwrap_or_default(), "gwei").unwrap_or_else(|_| "N/A".to_string()));
+    print_fee_info(tx);
     println!("Gas Used: {:?}", tx.gas);
     println!("Nonce: {:?}", tx.nonce);
 
@@ -119,43 +224,161 @@ fn print_transaction_info(tx: &Transaction) {
     }
     println!("Chain ID: {:?}", tx.chain_id.unwrap_or_default());
 
-    // Token transfer information
-    match extract_token_info(tx) {
-        Ok(Some(token_info)) => {
-            println!("Token Transfer Detected:");
-            println!("  Token Address: {}", token_info.token);
-            println!("  Token Amount: {}", ethers::utils::format_units(token_info.amount, "ether").unwrap_or_else(|_| "N/A".to_string()));
+    // Router swap information (a `multicall` can hold more than one leg)
+    if swaps.is_empty() {
+        println!("Token Information: Not available");
+    } else {
+        for (i, swap) in swaps.iter().enumerate() {
+            if swaps.len() > 1 {
+                println!("Swap Leg {} of {}:", i + 1, swaps.len());
+            }
+            print_swap_info(swap, resolver).await;
+        }
+    }
+
+    // `TRACE_MODE=1` additionally walks the transaction's internal call tree
+    // to catch token flows a proxy, aggregator, or multicall hides from the
+    // top-level calldata. Not every RPC endpoint exposes the trace
+    // namespace, so a failure here is reported but not fatal.
+    if env::var("TRACE_MODE").map(|v| v == "1").unwrap_or(false) {
+        match trace::trace_token_flows(resolver.provider().as_ref(), tx.hash).await {
+            Ok(flows) => print_token_flows(&flows),
+            Err(e) => eprintln!("Error tracing transaction {:?}: {:?}", tx.hash, e),
         }
-        Ok(None) => println!("Token Information: Not available"),
-        Err(e) => eprintln!("Error parsing token data: {:?}", e),
     }
 
     println!("============================================================");
 }
 
-// Structure for storing token information
-struct TokenInfo {
-    token: String,
-    amount: U256,
+fn print_token_flows(flows: &[trace::TokenFlow]) {
+    if flows.is_empty() {
+        println!("Traced Token Flows: none found");
+        return;
+    }
+    println!("Traced Token Flows:");
+    for flow in flows {
+        let token = if flow.token.is_zero() {
+            "ETH".to_string()
+        } else {
+            format!("{:?}", flow.token)
+        };
+        println!(
+            "  {} : {:?} -> {:?} : {}",
+            token, flow.from, flow.to, flow.amount
+        );
+    }
+}
+
+/// Transaction type discriminants as assigned by EIP-2718 (the only ones
+/// Ethereum mainnet currently uses).
+const TX_TYPE_LEGACY: u64 = 0;
+const TX_TYPE_EIP2930: u64 = 1;
+const TX_TYPE_EIP1559: u64 = 2;
+
+/// Prints gas-pricing fields, accounting for the fact that legacy, EIP-2930
+/// and EIP-1559 transactions each bid for gas differently.
+fn print_fee_info(tx: &Transaction) {
+    let tx_type = tx.transaction_type.map(|t| t.as_u64()).unwrap_or(TX_TYPE_LEGACY);
+    let type_label = match tx_type {
+        TX_TYPE_LEGACY => "Legacy",
+        TX_TYPE_EIP2930 => "EIP-2930 (access list)",
+        TX_TYPE_EIP1559 => "EIP-1559",
+        _ => "Unknown",
+    };
+    println!("Transaction Type: {} ({})", tx_type, type_label);
+
+    match (tx.max_fee_per_gas, tx.max_priority_fee_per_gas) {
+        (Some(max_fee), Some(max_priority_fee)) => {
+            println!(
+                "Max Fee Per Gas (Gwei): {}",
+                ethers::utils::format_units(max_fee, "gwei").unwrap_or_else(|_| "N/A".to_string())
+            );
+            println!(
+                "Max Priority Fee Per Gas (Gwei): {}",
+                ethers::utils::format_units(max_priority_fee, "gwei").unwrap_or_else(|_| "N/A".to_string())
+            );
+            // Without the block's base fee we can't compute the true
+            // effective gas price; the max fee is the tightest upper bound
+            // we can report from the transaction alone.
+            println!(
+                "Effective Gas Price Estimate (Gwei): {}",
+                ethers::utils::format_units(max_fee, "gwei").unwrap_or_else(|_| "N/A".to_string())
+            );
+        }
+        _ => {
+            println!(
+                "Gas Price (Gwei): {}",
+                ethers::utils::format_units(tx.gas_price.unwrap_or_default(), "gwei").unwrap_or_else(|_| "N/A".to_string())
+            );
+        }
+    }
+
+    if let Some(access_list) = &tx.access_list {
+        if access_list.0.is_empty() {
+            println!("Access List: (empty)");
+        } else {
+            println!("Access List:");
+            for item in &access_list.0 {
+                println!("  Address: {:?}", item.address);
+                for key in &item.storage_keys {
+                    println!("    Storage Key: {:?}", key);
+                }
+            }
+        }
+    }
 }
 
-// Function to extract token information from a transaction (if applicable)
-fn extract_token_info(tx: &Transaction) -> Result<Option<TokenInfo>, TrackerError> {
-    // Check if this is a token interaction transaction (e.g., ERC20)
-    // In this case, we check by the function signature `transfer` (0xa9059cbb)
-    let data = &tx.input;
-    {
-        if data.0.starts_with(&[0xa9, 0x05, 0x9c, 0xbb]) && data.0.len() == 68 {
-            // Extract the recipient address and token amount
-            let token_address = hex::encode(&data.0[16..36]);
-            let token_amount = U256::from_big_endian(&data.0[36..68]);
-
-            // Return token information
-            return Ok(Some(TokenInfo {
-                token: format!("0x{}", token_address),
-                amount: token_amount,
-            }));
+async fn print_swap_info<M: Middleware + 'static>(
+    swap: &SwapInfo,
+    resolver: &mut metadata::TokenMetadataResolver<M>,
+) {
+    println!("Swap Detected:");
+
+    let token_in_metadata = match swap.token_in {
+        Some(token) => Some(resolver.resolve(token).await),
+        None => None,
+    };
+    let token_out_metadata = match swap.token_out {
+        Some(token) => Some(resolver.resolve(token).await),
+        None => None,
+    };
+
+    println!(
+        "  Token In: {}",
+        describe_token(swap.token_in, token_in_metadata.as_ref())
+    );
+    println!(
+        "  Token Out: {}",
+        describe_token(swap.token_out, token_out_metadata.as_ref())
+    );
+    println!(
+        "  Fee Tier: {}",
+        swap.fee.map(|f| f.to_string()).unwrap_or_else(|| "N/A".to_string())
+    );
+    println!(
+        "  Amount In: {}",
+        match (swap.amount_in, &token_in_metadata) {
+            (Some(amount), Some(token)) => metadata::format_amount(amount, token.decimals),
+            _ => "N/A".to_string(),
         }
+    );
+    println!(
+        "  Amount Out: {}",
+        match (swap.amount_out, &token_out_metadata) {
+            (Some(amount), Some(token)) => metadata::format_amount(amount, token.decimals),
+            _ => "N/A".to_string(),
+        }
+    );
+    println!(
+        "  Recipient: {}",
+        swap.recipient.map(|a| format!("{:?}", a)).unwrap_or_else(|| "N/A".to_string())
+    );
+}
+
+fn describe_token(address: Option<Address>, metadata: Option<&metadata::TokenMetadata>) -> String {
+    match (address, metadata) {
+        (Some(address), Some(token)) => format!("{:?} ({})", address, token.symbol),
+        (Some(address), None) => format!("{:?}", address),
+        (None, _) => "N/A".to_string(),
     }
-    Ok(None)
 }
@@ -0,0 +1,104 @@
+use ethers::prelude::*;
+use ethers::types::{Address, Chain, U256};
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+
+// Minimal read-only ERC20 metadata interface, queried via `eth_call`.
+abigen!(
+    Erc20Metadata,
+    r#"[
+        function symbol() external view returns (string)
+        function name() external view returns (string)
+        function decimals() external view returns (uint8)
+    ]"#
+);
+
+#[derive(Debug, Clone)]
+pub struct TokenMetadata {
+    pub symbol: String,
+    pub name: String,
+    pub decimals: u8,
+}
+
+impl Default for TokenMetadata {
+    fn default() -> Self {
+        Self {
+            symbol: "UNKNOWN".to_string(),
+            name: "Unknown Token".to_string(),
+            decimals: 18,
+        }
+    }
+}
+
+/// Resolves and caches `symbol()`/`name()`/`decimals()` for token addresses,
+/// so formatting a token amount never assumes 18 decimals (which is wrong
+/// for tokens like USDC) and repeated swaps on the same token don't re-issue
+/// the same three `eth_call`s.
+pub struct TokenMetadataResolver<M> {
+    provider: Arc<M>,
+    cache: HashMap<Address, TokenMetadata>,
+}
+
+impl<M: Middleware + 'static> TokenMetadataResolver<M> {
+    pub fn new(provider: Arc<M>) -> Self {
+        Self {
+            provider,
+            cache: HashMap::new(),
+        }
+    }
+
+    pub fn provider(&self) -> &Arc<M> {
+        &self.provider
+    }
+
+    pub async fn resolve(&mut self, token: Address) -> TokenMetadata {
+        if let Some(metadata) = self.cache.get(&token) {
+            return metadata.clone();
+        }
+
+        let contract = Erc20Metadata::new(token, self.provider.clone());
+        let symbol = contract
+            .symbol()
+            .call()
+            .await
+            .unwrap_or_else(|_| "UNKNOWN".to_string());
+        let name = contract
+            .name()
+            .call()
+            .await
+            .unwrap_or_else(|_| "Unknown Token".to_string());
+        let decimals = contract.decimals().call().await.unwrap_or(18);
+
+        let metadata = TokenMetadata {
+            symbol,
+            name,
+            decimals,
+        };
+        self.cache.insert(token, metadata.clone());
+        metadata
+    }
+}
+
+/// Formats `amount` using the token's real decimals instead of always
+/// assuming 18 (ether).
+pub fn format_amount(amount: U256, decimals: u8) -> String {
+    ethers::utils::format_units(amount, decimals as u32).unwrap_or_else(|_| "N/A".to_string())
+}
+
+/// Decimal-normalized `f64` representation of `amount`, used to rank
+/// transactions by real token value instead of raw integer `U256`, which
+/// would otherwise favor high-decimal tokens.
+pub fn normalized_amount(amount: U256, decimals: u8) -> f64 {
+    format_amount(amount, decimals).parse().unwrap_or(0.0)
+}
+
+/// Looks up the verified contract name (e.g. "SwapRouter02") for a known
+/// router/pool contract via the Etherscan API, when `ETHERSCAN_API_KEY` is
+/// configured. Best-effort: returns `None` on any lookup failure.
+pub async fn label_known_contract(address: Address) -> Option<String> {
+    let api_key = env::var("ETHERSCAN_API_KEY").ok()?;
+    let client = ethers::etherscan::Client::new(Chain::Mainnet, api_key).ok()?;
+    let metadata = client.contract_source_code(address).await.ok()?;
+    metadata.items.into_iter().next().map(|item| item.contract_name)
+}
@@ -0,0 +1,178 @@
+use crate::connection;
+use crate::TrackerError;
+use ethers::abi::{decode, ParamType};
+use ethers::providers::{Http, Middleware, Provider, QuorumProvider, StreamExt, Ws};
+use ethers::types::{Address, Filter, Log, ValueOrArray, H256, I256, U256};
+use std::sync::Arc;
+
+/// Signature of the Uniswap V3 pool `Swap` event; its keccak256 hash (see
+/// `swap_event_topic`) is the topic0 the pool contract emits on every swap.
+const SWAP_EVENT_SIGNATURE: &str = "Swap(address,address,int256,int256,uint160,uint128,int24)";
+
+/// A decoded Uniswap V3 pool `Swap` log: the confirmed, on-chain effect of a
+/// swap, as opposed to the router calldata we only *intend* to execute.
+#[derive(Debug, Clone)]
+pub struct PoolSwap {
+    pub sender: Address,
+    pub recipient: Address,
+    pub amount0: I256,
+    pub amount1: I256,
+    pub sqrt_price_x96: U256,
+    pub liquidity: u128,
+    pub tick: i32,
+}
+
+fn swap_event_topic() -> H256 {
+    H256::from(ethers::utils::keccak256(SWAP_EVENT_SIGNATURE.as_bytes()))
+}
+
+/// Subscribes to `Swap` logs on `pool_address` and prints each one as it
+/// arrives. This is the confirmed-log counterpart to the pending-tx mode in
+/// `main`: no per-tx `get_transaction` round trip, and the amounts come
+/// straight from the event instead of being inferred from calldata.
+///
+/// `quorum_provider`, when configured (see `connection::build_quorum_provider`),
+/// gives logs mode the same guarantee the pending-tx path has: a log is only
+/// accepted once its transaction is also visible to a quorum of RPC backends,
+/// filtering out a single reorg'd or spoofed view.
+///
+/// A `SubscriptionStream` only ever ends when the underlying WebSocket
+/// connection drops, so this never returns `Ok(())` — it either runs
+/// forever or returns an error the caller should treat as "reconnect".
+pub async fn run(
+    provider: Arc<Provider<Ws>>,
+    pool_address: Address,
+    quorum_provider: &Option<Provider<QuorumProvider<Http>>>,
+) -> Result<(), TrackerError> {
+    let filter = Filter::new()
+        .address(ValueOrArray::Value(pool_address))
+        .topic0(swap_event_topic());
+
+    let mut stream = provider
+        .subscribe_logs(&filter)
+        .await
+        .map_err(TrackerError::TransactionRetrieval)?;
+
+    println!("Waiting for Swap events on pool {:?}...", pool_address);
+    while let Some(log) = stream.next().await {
+        if let Some(tx_hash) = log.transaction_hash {
+            if !connection::confirm_via_quorum(quorum_provider, tx_hash).await {
+                println!("Skipping log in {:?}: not confirmed by RPC quorum", tx_hash);
+                continue;
+            }
+        }
+
+        match decode_swap_log(&log) {
+            Ok(swap) => print_pool_swap(&log, &swap),
+            Err(e) => eprintln!("Error decoding swap log: {:?}", e),
+        }
+    }
+
+    Err(TrackerError::TransactionParsing(
+        "log subscription stream ended; WebSocket connection dropped".to_string(),
+    ))
+}
+
+fn decode_swap_log(log: &Log) -> Result<PoolSwap, TrackerError> {
+    if log.topics.len() < 3 {
+        return Err(TrackerError::TransactionParsing(
+            "swap log is missing the indexed sender/recipient topics".to_string(),
+        ));
+    }
+    let sender = Address::from(log.topics[1]);
+    let recipient = Address::from(log.topics[2]);
+
+    let tokens = decode(
+        &[
+            ParamType::Int(256),
+            ParamType::Int(256),
+            ParamType::Uint(160),
+            ParamType::Uint(128),
+            ParamType::Int(24),
+        ],
+        &log.data,
+    )
+    .map_err(|e| TrackerError::TransactionParsing(format!("failed to decode swap log data: {e}")))?;
+
+    let amount0 = I256::from_raw(tokens[0].clone().into_int().ok_or_else(|| {
+        TrackerError::TransactionParsing("amount0 was not an int256".to_string())
+    })?);
+    let amount1 = I256::from_raw(tokens[1].clone().into_int().ok_or_else(|| {
+        TrackerError::TransactionParsing("amount1 was not an int256".to_string())
+    })?);
+    let sqrt_price_x96 = tokens[2].clone().into_uint().ok_or_else(|| {
+        TrackerError::TransactionParsing("sqrtPriceX96 was not a uint160".to_string())
+    })?;
+    let liquidity = tokens[3]
+        .clone()
+        .into_uint()
+        .ok_or_else(|| TrackerError::TransactionParsing("liquidity was not a uint128".to_string()))?
+        .as_u128();
+    let tick = sign_extend_i24(tokens[4].clone().into_int().ok_or_else(|| {
+        TrackerError::TransactionParsing("tick was not an int24".to_string())
+    })?);
+
+    Ok(PoolSwap {
+        sender,
+        recipient,
+        amount0,
+        amount1,
+        sqrt_price_x96,
+        liquidity,
+        tick,
+    })
+}
+
+/// `ethers::abi` decodes `int24` into the low 24 bits of a `U256`; sign-extend
+/// it to a proper `i32` tick value.
+fn sign_extend_i24(raw: U256) -> i32 {
+    let value = raw.low_u32() & 0x00FF_FFFF;
+    if value & 0x0080_0000 != 0 {
+        (value | 0xFF00_0000) as i32
+    } else {
+        value as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_extend_i24_keeps_positive_values_unchanged() {
+        assert_eq!(sign_extend_i24(U256::from(0)), 0);
+        assert_eq!(sign_extend_i24(U256::from(887_272)), 887_272);
+    }
+
+    #[test]
+    fn sign_extend_i24_sign_extends_negative_values() {
+        // -1 as a 24-bit two's complement value is 0x00FFFFFF.
+        assert_eq!(sign_extend_i24(U256::from(0x00FF_FFFFu32)), -1);
+        // MIN_TICK (-887272) as a 24-bit two's complement value.
+        assert_eq!(sign_extend_i24(U256::from(0x00F2_7618u32)), -887_272);
+    }
+
+    #[test]
+    fn sign_extend_i24_ignores_bits_above_the_24th() {
+        // Bits above bit 23 must not leak into the result.
+        assert_eq!(sign_extend_i24(U256::from(0xFF00_0001u32)), 1);
+    }
+}
+
+fn print_pool_swap(log: &Log, swap: &PoolSwap) {
+    println!("==================== Pool Swap Event ====================");
+    if let Some(block_number) = log.block_number {
+        println!("Block Number: {:?}", block_number);
+    }
+    if let Some(tx_hash) = log.transaction_hash {
+        println!("Transaction Hash: {:?}", tx_hash);
+    }
+    println!("Sender: {:?}", swap.sender);
+    println!("Recipient: {:?}", swap.recipient);
+    println!("Amount0: {}", swap.amount0);
+    println!("Amount1: {}", swap.amount1);
+    println!("Sqrt Price X96: {}", swap.sqrt_price_x96);
+    println!("Liquidity: {}", swap.liquidity);
+    println!("Tick: {}", swap.tick);
+    println!("===========================================================");
+}
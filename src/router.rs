@@ -0,0 +1,208 @@
+use crate::TrackerError;
+use ethers::prelude::*;
+use ethers::types::{Address, Bytes, U256};
+
+// Human-readable ABI for the subset of the Uniswap V3 `SwapRouter` we care about.
+// `abigen!` generates a `SwapRouterCalls` enum (one variant per function) whose
+// `decode` tries each selector in turn, plus the per-function param structs.
+abigen!(
+    SwapRouter,
+    r#"[
+        function exactInputSingle((address tokenIn,address tokenOut,uint24 fee,address recipient,uint256 deadline,uint256 amountIn,uint256 amountOutMinimum,uint160 sqrtPriceLimitX96) params) external payable returns (uint256 amountOut)
+        function exactOutputSingle((address tokenIn,address tokenOut,uint24 fee,address recipient,uint256 deadline,uint256 amountOut,uint256 amountInMaximum,uint160 sqrtPriceLimitX96) params) external payable returns (uint256 amountIn)
+        function exactInput((bytes path,address recipient,uint256 deadline,uint256 amountIn,uint256 amountOutMinimum) params) external payable returns (uint256 amountOut)
+        function exactOutput((bytes path,address recipient,uint256 deadline,uint256 amountOut,uint256 amountInMaximum) params) external payable returns (uint256 amountIn)
+        function multicall(bytes[] data) external payable returns (bytes[] results)
+    ]"#
+);
+
+const PATH_ADDRESS_LEN: usize = 20;
+const PATH_FEE_LEN: usize = 3;
+
+/// Decoded summary of a Uniswap V3 `SwapRouter` call, richer than a raw
+/// ERC20 `transfer` so the listener can report actual swap parameters.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SwapInfo {
+    pub token_in: Option<Address>,
+    pub token_out: Option<Address>,
+    pub fee: Option<u32>,
+    pub amount_in: Option<U256>,
+    pub amount_out: Option<U256>,
+    pub recipient: Option<Address>,
+}
+
+/// Decodes `tx.input` against the known `SwapRouter` selectors, unwrapping
+/// `multicall(bytes[])` batches recursively. A single call decodes to at
+/// most one leg; a `multicall` can decode to several (e.g. a split-route
+/// trade across two `exactInputSingle` calls), so this always returns every
+/// leg it found rather than just the first. Returns an empty `Vec` when the
+/// calldata doesn't match any known router method.
+pub fn decode_swap_calldata(data: &Bytes) -> Result<Vec<SwapInfo>, TrackerError> {
+    let call = match SwapRouterCalls::decode(data) {
+        Ok(call) => call,
+        Err(_) => return Ok(Vec::new()),
+    };
+    decode_call(call)
+}
+
+fn decode_call(call: SwapRouterCalls) -> Result<Vec<SwapInfo>, TrackerError> {
+    match call {
+        SwapRouterCalls::ExactInputSingle(c) => Ok(vec![SwapInfo {
+            token_in: Some(c.params.token_in),
+            token_out: Some(c.params.token_out),
+            fee: Some(c.params.fee),
+            amount_in: Some(c.params.amount_in),
+            amount_out: None,
+            recipient: Some(c.params.recipient),
+        }]),
+        SwapRouterCalls::ExactOutputSingle(c) => Ok(vec![SwapInfo {
+            token_in: Some(c.params.token_in),
+            token_out: Some(c.params.token_out),
+            fee: Some(c.params.fee),
+            amount_in: None,
+            amount_out: Some(c.params.amount_out),
+            recipient: Some(c.params.recipient),
+        }]),
+        SwapRouterCalls::ExactInput(c) => {
+            let (token_in, token_out, fee) = decode_path(&c.params.path)?;
+            Ok(vec![SwapInfo {
+                token_in,
+                token_out,
+                fee,
+                amount_in: Some(c.params.amount_in),
+                amount_out: None,
+                recipient: Some(c.params.recipient),
+            }])
+        }
+        SwapRouterCalls::ExactOutput(c) => {
+            // `exactOutput` paths are encoded in reverse (token_out first), so
+            // the endpoints swap roles and `fee` is that of the hop into `token_out`.
+            let (token_out, token_in, fee) = decode_path(&c.params.path)?;
+            Ok(vec![SwapInfo {
+                token_in,
+                token_out,
+                fee,
+                amount_in: None,
+                amount_out: Some(c.params.amount_out),
+                recipient: Some(c.params.recipient),
+            }])
+        }
+        SwapRouterCalls::Multicall(c) => {
+            // Multicall batches several router calls together; collect a
+            // swap leg for every inner call that decodes as one instead of
+            // stopping at the first. One malformed leg (e.g. a short
+            // `exactInput` path) must not throw away the legs already
+            // decoded from its siblings, so log and skip instead of `?`.
+            let mut legs = Vec::new();
+            for inner in c.data {
+                match decode_swap_calldata(&Bytes::from(inner)) {
+                    Ok(inner_legs) => legs.extend(inner_legs),
+                    Err(e) => eprintln!("Error decoding multicall leg: {:?}", e),
+                }
+            }
+            Ok(legs)
+        }
+    }
+}
+
+/// Decodes the packed `exactInput`/`exactOutput` path
+/// (`token (20) | fee (3) | token (20) | fee (3) | ... | token (20)`).
+/// For multi-hop paths this reports the overall `token_in`/`token_out` and
+/// the fee of the first hop.
+fn decode_path(path: &Bytes) -> Result<(Option<Address>, Option<Address>, Option<u32>), TrackerError> {
+    if path.len() < 2 * PATH_ADDRESS_LEN + PATH_FEE_LEN {
+        return Err(TrackerError::TransactionParsing(
+            "swap path too short to contain a token pair".to_string(),
+        ));
+    }
+
+    let token_in = Address::from_slice(&path[0..PATH_ADDRESS_LEN]);
+    let fee_bytes = &path[PATH_ADDRESS_LEN..PATH_ADDRESS_LEN + PATH_FEE_LEN];
+    let fee = u32::from_be_bytes([0, fee_bytes[0], fee_bytes[1], fee_bytes[2]]);
+    let token_out = Address::from_slice(&path[path.len() - PATH_ADDRESS_LEN..]);
+
+    Ok((Some(token_in), Some(token_out), Some(fee)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packed_path(token_in: Address, fee: u32, token_out: Address) -> Bytes {
+        let mut bytes = token_in.as_bytes().to_vec();
+        bytes.extend_from_slice(&fee.to_be_bytes()[1..4]);
+        bytes.extend_from_slice(token_out.as_bytes());
+        Bytes::from(bytes)
+    }
+
+    #[test]
+    fn decode_path_rejects_paths_shorter_than_a_token_pair() {
+        let short = Bytes::from(vec![0u8; PATH_ADDRESS_LEN]);
+        assert!(decode_path(&short).is_err());
+    }
+
+    #[test]
+    fn decode_path_extracts_token_pair_and_first_hop_fee() {
+        let token_in = Address::repeat_byte(0x11);
+        let token_out = Address::repeat_byte(0x22);
+        let path = packed_path(token_in, 3000, token_out);
+
+        let (decoded_in, decoded_out, fee) = decode_path(&path).unwrap();
+        assert_eq!(decoded_in, Some(token_in));
+        assert_eq!(decoded_out, Some(token_out));
+        assert_eq!(fee, Some(3000));
+    }
+
+    #[test]
+    fn decode_path_multi_hop_reports_overall_endpoints() {
+        let token_in = Address::repeat_byte(0x11);
+        let middle = Address::repeat_byte(0x33);
+        let token_out = Address::repeat_byte(0x22);
+
+        let mut path = packed_path(token_in, 500, middle).to_vec();
+        path.extend_from_slice(&packed_path(middle, 3000, token_out)[PATH_ADDRESS_LEN..]);
+
+        let (decoded_in, decoded_out, fee) = decode_path(&Bytes::from(path)).unwrap();
+        assert_eq!(decoded_in, Some(token_in));
+        assert_eq!(decoded_out, Some(token_out));
+        // Reports the first hop's fee, not the last.
+        assert_eq!(fee, Some(500));
+    }
+
+    #[test]
+    fn multicall_collects_every_decodable_leg_and_skips_malformed_ones() {
+        let good_leg = ExactInputSingleCall {
+            params: ExactInputSingleParams {
+                token_in: Address::repeat_byte(0x11),
+                token_out: Address::repeat_byte(0x22),
+                fee: 3000,
+                recipient: Address::repeat_byte(0x33),
+                deadline: U256::from(1u64),
+                amount_in: U256::from(1_000u64),
+                amount_out_minimum: U256::zero(),
+                sqrt_price_limit_x96: U256::zero(),
+            },
+        };
+        let malformed_leg = ExactInputCall {
+            params: ExactInputParams {
+                path: Bytes::from(vec![0u8; 4]),
+                recipient: Address::zero(),
+                deadline: U256::zero(),
+                amount_in: U256::zero(),
+                amount_out_minimum: U256::zero(),
+            },
+        };
+
+        let multicall = MulticallCall {
+            data: vec![good_leg.encode().into(), malformed_leg.encode().into()],
+        };
+
+        let legs = decode_swap_calldata(&Bytes::from(multicall.encode())).unwrap();
+
+        // The malformed leg is skipped, not propagated as an error that
+        // would otherwise discard the good leg too.
+        assert_eq!(legs.len(), 1);
+        assert_eq!(legs[0].token_in, Some(Address::repeat_byte(0x11)));
+        assert_eq!(legs[0].amount_in, Some(U256::from(1_000u64)));
+    }
+}
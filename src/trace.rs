@@ -0,0 +1,95 @@
+use crate::TrackerError;
+use ethers::providers::Middleware;
+use ethers::types::trace::Action;
+use ethers::types::{Address, Call, H256, U256};
+
+const TRANSFER_SELECTOR: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+const TRANSFER_FROM_SELECTOR: [u8; 4] = [0x23, 0xb8, 0x72, 0xdd];
+
+/// A single token movement surfaced while walking a transaction's internal
+/// call tree: either a native ETH value transfer (`token == Address::zero()`)
+/// or an ERC20 `transfer`/`transferFrom` issued by an internal call.
+#[derive(Debug, Clone)]
+pub struct TokenFlow {
+    pub token: Address,
+    pub from: Address,
+    pub to: Address,
+    pub amount: U256,
+}
+
+/// Opt-in deep trace of a transaction's internal calls via `trace_transaction`,
+/// surfacing token movements that top-level calldata decoding misses when the
+/// swap is routed through a proxy, aggregator, or `multicall`. Requires the
+/// RPC endpoint to expose the `trace` namespace; not every provider does, so
+/// callers should guard this behind a flag and treat errors as "unavailable"
+/// rather than fatal.
+pub async fn trace_token_flows<M: Middleware>(
+    provider: &M,
+    tx_hash: H256,
+) -> Result<Vec<TokenFlow>, TrackerError> {
+    let traces = provider.trace_transaction(tx_hash).await.map_err(|e| {
+        TrackerError::TransactionParsing(format!(
+            "trace_transaction failed (is the `trace` namespace enabled on this RPC?): {e}"
+        ))
+    })?;
+
+    let mut flows = Vec::new();
+    for trace in traces {
+        // A reverted sub-call never actually moved value or tokens, even
+        // though its `Action::Call` is still present in the trace tree.
+        if trace.error.is_some() {
+            continue;
+        }
+
+        let Action::Call(call) = trace.action else {
+            continue;
+        };
+
+        // Internal native ETH transfer.
+        if !call.value.is_zero() {
+            flows.push(TokenFlow {
+                token: Address::zero(),
+                from: call.from,
+                to: call.to,
+                amount: call.value,
+            });
+        }
+
+        // Internal ERC20 `transfer`/`transferFrom` calls a router or
+        // aggregator issues on the token contract directly, invisible to a
+        // decoder that only looks at the outermost calldata.
+        if let Some(flow) = decode_erc20_transfer_call(&call) {
+            flows.push(flow);
+        }
+    }
+
+    Ok(flows)
+}
+
+fn decode_erc20_transfer_call(call: &Call) -> Option<TokenFlow> {
+    let data = &call.input.0;
+    if data.starts_with(&TRANSFER_SELECTOR) && data.len() == 68 {
+        let to = Address::from_slice(&data[16..36]);
+        let amount = U256::from_big_endian(&data[36..68]);
+        return Some(TokenFlow {
+            token: call.to,
+            from: call.from,
+            to,
+            amount,
+        });
+    }
+
+    if data.starts_with(&TRANSFER_FROM_SELECTOR) && data.len() == 100 {
+        let from = Address::from_slice(&data[16..36]);
+        let to = Address::from_slice(&data[48..68]);
+        let amount = U256::from_big_endian(&data[68..100]);
+        return Some(TokenFlow {
+            token: call.to,
+            from,
+            to,
+            amount,
+        });
+    }
+
+    None
+}